@@ -1,5 +1,11 @@
-use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use serde_json::Value;
+
+mod trajectory;
+
+use trajectory::Conditions;
 
 /// Calculate derived golf shot values from measured data.
 ///
@@ -13,18 +19,69 @@ use pyo3::exceptions::PyValueError;
 ///   - ball_speed_meters_per_second: f64
 ///   - vertical_launch_angle_degrees: f64
 ///   Optional fields include horizontal_launch_angle_degrees, total_spin_rpm,
-///   spin_axis_degrees, backspin_rpm, sidespin_rpm, and others.
+///   spin_axis_degrees, backspin_rpm, sidespin_rpm, and others. Playing
+///   conditions can also be supplied to move the simulation away from its
+///   default conditions (sea level, 25 °C, 50 % relative humidity,
+///   1013.25 hPa):
+///   - temperature_celsius: f64
+///   - pressure_hpa: f64 (sea-level-referenced reading, as weather
+///     stations/forecast APIs report it; always adjusted for
+///     `elevation_meters` via the barometric formula, defaulting to 1013.25
+///     hPa when omitted — a no-op at sea level)
+///   - relative_humidity_percent: f64
+///   - elevation_meters: f64
+///   - wind_speed_meters_per_second: f64
+///   - wind_direction_degrees: f64 (0 = helping, 180 = into, 90/270 = crosswind)
+///   The aerodynamic model itself can be tuned via optional overrides to the
+///   spin-dependent drag/lift coefficients (Cd = cd0 + cd_spin·S², Cl =
+///   cl0·S/(cl1 + S), where S is the dimensionless spin factor r·ω/v_rel):
+///   - cd0, cd_spin: f64
+///   - cl0, cl1: f64
+///   - include_trajectory: bool (when true, the output carries the full
+///     time-stepped flight path instead of just the landing summary)
 ///
 /// # Returns
 ///
-/// JSON string containing derived values including:
+/// JSON string with the original input echoed back plus an `open_golf_coach`
+/// object (the same nesting `opengolfcoach::calculate_derived_values` uses)
+/// containing derived values including:
 /// - carry_distance_meters
 /// - total_distance_meters
 /// - offline_distance_meters
-/// - backspin_rpm / sidespin_rpm
+/// - total_spin_rpm / spin_axis_degrees and backspin_rpm / sidespin_rpm,
+///   resolved consistently from whichever representation was supplied
+/// - spin_efficiency_percent (fraction of total spin contributing to lift
+///   rather than gyroscopic spin, when total spin and axis are available)
+/// - horizontal_break_meters / vertical_break_meters (lateral and vertical
+///   displacement attributable purely to the Magnus force, found by
+///   integrating the shot twice with lift enabled and disabled; lateral is
+///   the difference in landing offline distance, vertical the difference in
+///   apex height)
 /// - shot_name, shot_rank, shot_color_rgb
 /// - club_speed_meters_per_second
 /// - smash_factor
+/// - air_density_kg_per_m3 (computed from temperature, pressure/elevation,
+///   and humidity, defaulting to the conditions listed above when none are
+///   given; reflects the conditions the carry distance was simulated under).
+///   `carry_distance_meters` / `offline_distance_meters` /
+///   `total_distance_meters` come from this module's wind-and-spin-aware
+///   simulation (not `opengolfcoach`'s own constant-Cd/Cl core) whenever that
+///   simulation lands within its iteration budget, so they stay consistent
+///   with the Cd/Cl/trajectory fields below even when no playing-condition
+///   field above was supplied. If it does not land, the core's own values
+///   are kept and the simulation-derived fields are omitted.
+///   `total_distance_meters` keeps the core's own roll estimate so it stays
+///   carry-plus-roll instead of going stale.
+/// - drag_coefficient_at_launch / lift_coefficient_at_launch (Cd/Cl evaluated
+///   at the initial spin factor, for validating against launch-monitor carry
+///   numbers)
+/// - apex_height_meters, descent_angle_degrees, flight_time_seconds (only
+///   when `include_trajectory` is set)
+/// - trajectory_points (only when `include_trajectory` is set): an array of
+///   `{ time_seconds, x_meters, y_meters, z_meters,
+///   speed_meters_per_second, spin_rpm }` samples, one per integration step.
+///   Coordinates follow the project-wide convention also used by
+///   `opengolfcoach::Vector3`: +X forward, +Y right, +Z up.
 /// - and more (see API.md for complete schema)
 ///
 /// # Errors
@@ -54,9 +111,493 @@ use pyo3::exceptions::PyValueError;
 /// ```
 #[pyfunction]
 fn calculate_derived_values(json_input: &str) -> PyResult<String> {
-    // Call the underlying Rust core function
-    opengolfcoach::calculate_derived_values(json_input)
-        .map_err(|e| PyValueError::new_err(format!("Calculation failed: {}", e)))
+    calculate_derived_values_augmented(json_input).map_err(PyValueError::new_err)
+}
+
+/// Shared implementation behind `calculate_derived_values` and
+/// `calculate_derived_values_batch`, so both entry points return the same
+/// augmented schema instead of the batch path silently returning bare core
+/// output.
+fn calculate_derived_values_augmented(json_input: &str) -> Result<String, String> {
+    let result_json = opengolfcoach::calculate_derived_values(json_input)
+        .map_err(|e| format!("Calculation failed: {}", e))?;
+
+    // Spin-axis resolution and Magnus break are layered on top of the core
+    // result here. If the input or result can't be parsed, or the result has
+    // no `open_golf_coach` object, we fall back to the core result unchanged
+    // rather than failing the whole call; a derivation whose fields are
+    // missing is skipped while the others are still added.
+    Ok(augment_with_spin_and_break(json_input, &result_json).unwrap_or(result_json))
+}
+
+/// Resolve total spin + axis and backspin/sidespin to be consistent with
+/// each other, regardless of which representation the caller supplied.
+/// Falls back to the core result's backspin/sidespin when the input only
+/// gave a ball speed and angle (e.g. spin was itself derived by the core).
+///
+/// `derived_fields` is the `open_golf_coach` object from the core result, not
+/// the echoed-input-plus-wrapper top level.
+fn resolve_spin(
+    input: &Value,
+    derived_fields: &serde_json::Map<String, Value>,
+) -> Option<(f64, f64, f64, f64)> {
+    let field = |source: &Value, key: &str| source.get(key).and_then(Value::as_f64);
+
+    if let (Some(total_rpm), Some(axis_degrees)) = (
+        field(input, "total_spin_rpm"),
+        field(input, "spin_axis_degrees"),
+    ) {
+        let axis_radians = axis_degrees.to_radians();
+        let backspin_rpm = total_rpm * axis_radians.cos();
+        let sidespin_rpm = total_rpm * axis_radians.sin();
+        return Some((total_rpm, axis_degrees, backspin_rpm, sidespin_rpm));
+    }
+
+    let from_derived = |key: &str| derived_fields.get(key).and_then(Value::as_f64);
+    let backspin_rpm = field(input, "backspin_rpm").or_else(|| from_derived("backspin_rpm"));
+    let sidespin_rpm = field(input, "sidespin_rpm").or_else(|| from_derived("sidespin_rpm"));
+    let (backspin_rpm, sidespin_rpm) = (backspin_rpm?, sidespin_rpm?);
+    let total_rpm = backspin_rpm.hypot(sidespin_rpm);
+    let axis_degrees = sidespin_rpm.atan2(backspin_rpm).to_degrees();
+    Some((total_rpm, axis_degrees, backspin_rpm, sidespin_rpm))
+}
+
+/// Compute the lateral/vertical displacement attributable purely to the
+/// Magnus force, by re-running `with_lift`'s shot through the local
+/// integrator with lift coefficients zeroed out and differencing the two
+/// flights: the lateral break is the difference in landing offline distance,
+/// the vertical break the difference in apex height. `with_lift` must come
+/// from simulating the same shot
+/// (speed/angles/spin/conditions) with lift enabled.
+fn compute_magnus_break(
+    with_lift: &trajectory::TrajectoryResult,
+    ball_speed_mps: f64,
+    v_angle: f64,
+    h_angle: f64,
+    backspin_rpm: f64,
+    sidespin_rpm: f64,
+    conditions: &Conditions,
+) -> Option<(f64, f64)> {
+    let mut zero_lift_conditions = *conditions;
+    // cl0 = 0 makes Cl = 0 * S / (cl1 + S) = 0 regardless of spin; cl1 only
+    // needs to be non-zero so the (cl0, cl1) override pair is honored
+    // instead of falling back to the non-zero default cl0.
+    zero_lift_conditions.cl0 = Some(0.0);
+    zero_lift_conditions.cl1 = Some(1.0);
+
+    let zero_lift = trajectory::simulate(
+        ball_speed_mps,
+        v_angle,
+        h_angle,
+        backspin_rpm,
+        sidespin_rpm,
+        &zero_lift_conditions,
+    )?;
+
+    Some((
+        with_lift.offline_distance_meters - zero_lift.offline_distance_meters,
+        with_lift.apex_height_meters - zero_lift.apex_height_meters,
+    ))
+}
+
+/// Merge spin-axis resolution and environmental/trajectory/Magnus-break
+/// fields into a `calculate_derived_values` result. Returns `None` if
+/// `json_input` or `result_json` can't be parsed as JSON objects, or if the
+/// result has no `open_golf_coach` object to augment, in which case the
+/// caller should fall back to the unaugmented result.
+fn augment_with_spin_and_break(json_input: &str, result_json: &str) -> Option<String> {
+    let input: Value = serde_json::from_str(json_input).ok()?;
+    let mut result: Value = serde_json::from_str(result_json).ok()?;
+
+    let derived_snapshot = result.get("open_golf_coach")?.as_object()?.clone();
+    let spin = resolve_spin(&input, &derived_snapshot);
+
+    let derived_fields = result.get_mut("open_golf_coach")?.as_object_mut()?;
+
+    if let Some((total_rpm, axis_degrees, backspin_rpm, sidespin_rpm)) = spin {
+        let spin_efficiency_percent = if total_rpm > 0.0 {
+            Some((backspin_rpm / total_rpm) * 100.0)
+        } else {
+            None
+        };
+
+        derived_fields.insert("total_spin_rpm".to_string(), serde_json::json!(total_rpm));
+        derived_fields.insert(
+            "spin_axis_degrees".to_string(),
+            serde_json::json!(axis_degrees),
+        );
+        derived_fields.insert("backspin_rpm".to_string(), serde_json::json!(backspin_rpm));
+        derived_fields.insert("sidespin_rpm".to_string(), serde_json::json!(sidespin_rpm));
+        if let Some(spin_efficiency_percent) = spin_efficiency_percent {
+            derived_fields.insert(
+                "spin_efficiency_percent".to_string(),
+                serde_json::json!(spin_efficiency_percent),
+            );
+        }
+    }
+
+    augment_with_environment(&input, derived_fields);
+
+    serde_json::to_string(&result).ok()
+}
+
+/// Simulate the shot under the playing conditions supplied in `input`
+/// (elevation/temperature/humidity/pressure/wind, defaulting to ISA
+/// sea-level when none are given) and merge the results into
+/// `derived_fields`, unconditionally replacing `carry_distance_meters` /
+/// `offline_distance_meters` / `total_distance_meters` with this module's
+/// spin-dependent-Cd/Cl integrator. `derived_fields` is left unchanged only
+/// when the input lacks ball speed or vertical launch angle, or when the
+/// simulation does not land within its iteration budget. This runs — even for a plain
+/// shot with no environmental overrides — so the reported carry distance
+/// is always consistent with the `air_density_kg_per_m3` /
+/// `drag_coefficient_at_launch` / `lift_coefficient_at_launch` /
+/// `trajectory_points` this function also reports; gating the distance
+/// fields behind "did the caller override anything" left them sourced from
+/// `opengolfcoach`'s own constant-Cd/Cl core for the common case, silently
+/// disagreeing with the coefficients reported alongside them.
+fn augment_with_environment(input: &Value, derived_fields: &mut serde_json::Map<String, Value>) {
+    let ball_speed_mps = input
+        .get("ball_speed_meters_per_second")
+        .and_then(Value::as_f64);
+    let v_angle = input
+        .get("vertical_launch_angle_degrees")
+        .and_then(Value::as_f64);
+    let (ball_speed_mps, v_angle) = match (ball_speed_mps, v_angle) {
+        (Some(ball_speed_mps), Some(v_angle)) => (ball_speed_mps, v_angle),
+        _ => return,
+    };
+    let h_angle = input
+        .get("horizontal_launch_angle_degrees")
+        .and_then(Value::as_f64)
+        .unwrap_or(0.0);
+
+    let backspin_rpm = input
+        .get("backspin_rpm")
+        .and_then(Value::as_f64)
+        .or_else(|| derived_fields.get("backspin_rpm").and_then(Value::as_f64))
+        .unwrap_or(0.0);
+    let sidespin_rpm = input
+        .get("sidespin_rpm")
+        .and_then(Value::as_f64)
+        .or_else(|| derived_fields.get("sidespin_rpm").and_then(Value::as_f64))
+        .unwrap_or(0.0);
+
+    let conditions = Conditions::from_input(input);
+    let simulated = match trajectory::simulate(
+        ball_speed_mps,
+        v_angle,
+        h_angle,
+        backspin_rpm,
+        sidespin_rpm,
+        &conditions,
+    ) {
+        Some(simulated) => simulated,
+        // Didn't land within the iteration budget (e.g. an unrealistic
+        // tailwind/lift combination) — leave the core's own numbers alone
+        // rather than reporting a result that never converged.
+        None => return,
+    };
+
+    derived_fields.insert(
+        "air_density_kg_per_m3".to_string(),
+        serde_json::json!(simulated.air_density_kg_per_m3),
+    );
+    derived_fields.insert(
+        "drag_coefficient_at_launch".to_string(),
+        serde_json::json!(simulated.drag_coefficient_at_launch),
+    );
+    derived_fields.insert(
+        "lift_coefficient_at_launch".to_string(),
+        serde_json::json!(simulated.lift_coefficient_at_launch),
+    );
+
+    if let Some((horizontal_break_meters, vertical_break_meters)) = compute_magnus_break(
+        &simulated,
+        ball_speed_mps,
+        v_angle,
+        h_angle,
+        backspin_rpm,
+        sidespin_rpm,
+        &conditions,
+    ) {
+        derived_fields.insert(
+            "horizontal_break_meters".to_string(),
+            serde_json::json!(horizontal_break_meters),
+        );
+        derived_fields.insert(
+            "vertical_break_meters".to_string(),
+            serde_json::json!(vertical_break_meters),
+        );
+    }
+
+    let include_trajectory = input
+        .get("include_trajectory")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if include_trajectory {
+        derived_fields.insert(
+            "apex_height_meters".to_string(),
+            serde_json::json!(simulated.apex_height_meters),
+        );
+        derived_fields.insert(
+            "descent_angle_degrees".to_string(),
+            serde_json::json!(simulated.descent_angle_degrees),
+        );
+        derived_fields.insert(
+            "flight_time_seconds".to_string(),
+            serde_json::json!(simulated.flight_time_seconds),
+        );
+        let trajectory_points: Vec<Value> = simulated
+            .points
+            .iter()
+            .map(|point| {
+                serde_json::json!({
+                    "time_seconds": point.time_seconds,
+                    "x_meters": point.x_meters,
+                    "y_meters": point.y_meters,
+                    "z_meters": point.z_meters,
+                    "speed_meters_per_second": point.speed_meters_per_second,
+                    "spin_rpm": point.spin_rpm,
+                })
+            })
+            .collect();
+        derived_fields.insert(
+            "trajectory_points".to_string(),
+            serde_json::json!(trajectory_points),
+        );
+    }
+
+    // Always sourced from this module's integrator — `drag_coefficient_at_launch`
+    // /`lift_coefficient_at_launch` and, when requested, `trajectory_points`
+    // are already unconditionally derived from it above, so leaving
+    // carry/offline/total on the untouched core's constant-Cd/Cl numbers
+    // would make the response self-contradictory (reported coefficients
+    // that don't correspond to the reported carry distance). `conditions`
+    // defaults to the core's own ISA assumptions when nothing was
+    // overridden, so a plain shot with no environmental fields still gets
+    // the same physics the rest of this module's output reflects.
+    let roll_meters = match (
+        derived_fields.get("total_distance_meters").and_then(Value::as_f64),
+        derived_fields.get("carry_distance_meters").and_then(Value::as_f64),
+    ) {
+        (Some(total), Some(carry)) => total - carry,
+        _ => 0.0,
+    };
+
+    derived_fields.insert(
+        "carry_distance_meters".to_string(),
+        serde_json::json!(simulated.carry_distance_meters),
+    );
+    derived_fields.insert(
+        "offline_distance_meters".to_string(),
+        serde_json::json!(simulated.offline_distance_meters),
+    );
+    derived_fields.insert(
+        "total_distance_meters".to_string(),
+        serde_json::json!(simulated.carry_distance_meters + roll_meters),
+    );
+}
+
+/// Merge a shared config block into a single shot, without overwriting
+/// fields the shot already specifies.
+fn apply_shared_config(shot: &mut Value, config: &Value) {
+    if let (Value::Object(shot_fields), Value::Object(config_fields)) = (shot, config) {
+        for (key, value) in config_fields {
+            shot_fields
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+}
+
+/// Calculate derived golf shot values for many shots in a single call.
+///
+/// This avoids paying the JSON-parse and Python/Rust boundary overhead once
+/// per shot when a Home Assistant session ingests dozens of shots at a time.
+/// Shots are processed concurrently with rayon.
+///
+/// # Arguments
+///
+/// * `json_input` - JSON object of the form `{"shots": [...], "config": {...}}`.
+///   - shots: array of shot objects, each accepted by `calculate_derived_values`
+///   - config: optional object of shared fields (e.g. environmental
+///     conditions or drag/lift coefficient overrides) merged into every shot
+///     that doesn't already specify them
+///
+/// # Returns
+///
+/// JSON array of results in the same order as `shots`. Each element is
+/// either the normal `calculate_derived_values` output object, or
+/// `{"error": "..."}` if that shot failed to process, so one malformed row
+/// does not discard the rest of the session.
+///
+/// # Errors
+///
+/// Returns PyValueError if `json_input` itself is malformed or does not
+/// contain a `shots` array.
+#[pyfunction]
+fn calculate_derived_values_batch(json_input: &str) -> PyResult<String> {
+    let request: Value = serde_json::from_str(json_input)
+        .map_err(|e| PyValueError::new_err(format!("Invalid batch JSON: {}", e)))?;
+
+    let shots = request
+        .get("shots")
+        .and_then(Value::as_array)
+        .ok_or_else(|| PyValueError::new_err("Batch input must contain a \"shots\" array"))?;
+    let config = request.get("config");
+
+    let results: Vec<Value> = shots
+        .par_iter()
+        .map(|shot| {
+            let mut shot = shot.clone();
+            if let Some(config) = config {
+                apply_shared_config(&mut shot, config);
+            }
+
+            let shot_json = shot.to_string();
+            match calculate_derived_values_augmented(&shot_json) {
+                Ok(result_json) => serde_json::from_str(&result_json).unwrap_or_else(
+                    |e| serde_json::json!({ "error": format!("Malformed result JSON: {}", e) }),
+                ),
+                Err(e) => serde_json::json!({ "error": e }),
+            }
+        })
+        .collect();
+
+    serde_json::to_string(&results)
+        .map_err(|e| PyValueError::new_err(format!("Failed to serialize batch results: {}", e)))
+}
+
+/// Evaluate carry distance for one (launch angle, spin) trial, reusing the
+/// existing single-shot calculation and layering the trial's launch
+/// parameters over whatever environmental/coefficient fields were supplied.
+fn evaluate_carry(
+    base_input: &Value,
+    ball_speed: f64,
+    angle_degrees: f64,
+    spin_rpm: f64,
+) -> Option<f64> {
+    let mut shot = base_input.clone();
+    let shot_fields = shot.as_object_mut()?;
+    shot_fields.insert(
+        "ball_speed_meters_per_second".to_string(),
+        serde_json::json!(ball_speed),
+    );
+    shot_fields.insert(
+        "vertical_launch_angle_degrees".to_string(),
+        serde_json::json!(angle_degrees),
+    );
+    shot_fields.insert("total_spin_rpm".to_string(), serde_json::json!(spin_rpm));
+
+    let result_json = calculate_derived_values_augmented(&shot.to_string()).ok()?;
+    let result: Value = serde_json::from_str(&result_json).ok()?;
+    result
+        .get("open_golf_coach")?
+        .get("carry_distance_meters")?
+        .as_f64()
+}
+
+/// Evaluate carry distance at every (launch angle, spin rate) combination of
+/// the given grid at a fixed ball speed, returning `(angle, spin, carry)` for
+/// each point; points whose evaluation failed are left out.
+fn search_launch_grid(
+    base_input: &Value,
+    ball_speed: f64,
+    angles: impl Iterator<Item = f64> + Clone,
+    spins: impl Iterator<Item = f64> + Clone,
+) -> Vec<(f64, f64, f64)> {
+    angles
+        .flat_map(|angle| {
+            let spins = spins.clone();
+            spins.filter_map(move |spin| {
+                evaluate_carry(base_input, ball_speed, angle, spin)
+                    .map(|carry| (angle, spin, carry))
+            })
+        })
+        .collect()
+}
+
+/// Find the launch angle and spin rate that maximize carry distance for a
+/// fixed ball speed.
+///
+/// Implemented as a coarse-to-fine grid search over the existing trajectory
+/// integrator: a coarse grid (5-25 degrees in 1 degree steps, 1500-6000 rpm
+/// in 250 rpm steps) locates the best cell, which is then refined with a
+/// finer grid (+/- one coarse step, in 0.2 degree / 50 rpm steps) centered
+/// on that cell.
+///
+/// # Arguments
+///
+/// * `json_input` - JSON object with:
+///   - ball_speed_meters_per_second: f64 (required)
+///   - any of the environmental/coefficient override fields accepted by
+///     `calculate_derived_values`, held fixed across the search
+///
+/// # Returns
+///
+/// JSON object with:
+/// - optimal_launch_angle_degrees / optimal_spin_rpm
+/// - optimal_carry_distance_meters
+/// - sampled_grid: the coarse-grid samples as `{angle, spin, carry}`, so the
+///   UI can show how far a player's actual numbers are from their optimal
+///   launch window
+///
+/// # Errors
+///
+/// Returns PyValueError if `json_input` is malformed, missing
+/// `ball_speed_meters_per_second`, or if no grid point produced a valid
+/// carry distance.
+#[pyfunction]
+fn optimize_launch(json_input: &str) -> PyResult<String> {
+    let base_input: Value = serde_json::from_str(json_input)
+        .map_err(|e| PyValueError::new_err(format!("Invalid JSON: {}", e)))?;
+    let ball_speed = base_input
+        .get("ball_speed_meters_per_second")
+        .and_then(Value::as_f64)
+        .ok_or_else(|| PyValueError::new_err("Missing ball_speed_meters_per_second"))?;
+
+    let coarse_angles: Vec<f64> = (5..=25).map(|a| a as f64).collect();
+    let coarse_spins: Vec<f64> = (0..=18).map(|i| 1500.0 + i as f64 * 250.0).collect();
+    let coarse_grid = search_launch_grid(
+        &base_input,
+        ball_speed,
+        coarse_angles.iter().copied(),
+        coarse_spins.iter().copied(),
+    );
+
+    let (best_angle, best_spin, _) = coarse_grid
+        .iter()
+        .copied()
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .ok_or_else(|| PyValueError::new_err("No valid carry distance found on the coarse grid"))?;
+
+    let fine_angles = (-5..=5).map(|i| best_angle + i as f64 * 0.2);
+    let fine_spins = (-5..=5).map(|i| best_spin + i as f64 * 50.0);
+    let fine_grid = search_launch_grid(&base_input, ball_speed, fine_angles, fine_spins);
+
+    let (optimal_angle, optimal_spin, optimal_carry) = fine_grid
+        .iter()
+        .copied()
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .ok_or_else(|| {
+            PyValueError::new_err("No valid carry distance found on the refined grid")
+        })?;
+
+    let sampled_grid: Vec<Value> = coarse_grid
+        .iter()
+        .map(|(angle, spin, carry)| {
+            serde_json::json!({ "angle": angle, "spin": spin, "carry": carry })
+        })
+        .collect();
+
+    serde_json::to_string(&serde_json::json!({
+        "optimal_launch_angle_degrees": optimal_angle,
+        "optimal_spin_rpm": optimal_spin,
+        "optimal_carry_distance_meters": optimal_carry,
+        "sampled_grid": sampled_grid,
+    }))
+    .map_err(|e| PyValueError::new_err(format!("Failed to serialize result: {}", e)))
 }
 
 /// OpenGolfCoach Rust Extension Module
@@ -70,11 +611,212 @@ fn calculate_derived_values(json_input: &str) -> PyResult<String> {
 /// - Club speed and smash factor estimation
 /// - Coaching recommendations based on shot shape
 ///
-/// The module exposes a single function `calculate_derived_values` that accepts
-/// JSON input and returns JSON output for maximum flexibility and compatibility
+/// The module exposes `calculate_derived_values` for single shots,
+/// `calculate_derived_values_batch` for processing a session's worth of shots
+/// in parallel, and `optimize_launch` for finding a player's optimal launch
+/// window. All three accept JSON input and return JSON output for maximum
+/// flexibility and compatibility
 /// with the Home Assistant integration layer.
 #[pymodule]
 fn opengolfcoach_rust(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(calculate_derived_values, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_derived_values_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize_launch, m)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_shared_config_does_not_overwrite_existing_shot_fields() {
+        let mut shot = serde_json::json!({
+            "ball_speed_meters_per_second": 70.0,
+            "wind_speed_meters_per_second": 5.0,
+        });
+        let config = serde_json::json!({
+            "wind_speed_meters_per_second": 2.0,
+            "temperature_celsius": 30.0,
+        });
+
+        apply_shared_config(&mut shot, &config);
+
+        assert_eq!(
+            shot["wind_speed_meters_per_second"], 5.0,
+            "shot's own value must win over the shared config"
+        );
+        assert_eq!(
+            shot["temperature_celsius"], 30.0,
+            "fields absent from the shot should be filled in from the shared config"
+        );
+        assert_eq!(shot["ball_speed_meters_per_second"], 70.0);
+    }
+
+    #[test]
+    fn apply_shared_config_is_a_no_op_without_an_object_config() {
+        let mut shot = serde_json::json!({ "ball_speed_meters_per_second": 70.0 });
+        let config = serde_json::json!(null);
+
+        apply_shared_config(&mut shot, &config);
+
+        assert_eq!(shot["ball_speed_meters_per_second"], 70.0);
+    }
+
+    #[test]
+    fn batch_results_carry_the_same_augmentation_as_single_shot_calls() {
+        let batch_input = serde_json::json!({
+            "shots": [
+                {
+                    "ball_speed_meters_per_second": 70.0,
+                    "vertical_launch_angle_degrees": 12.5,
+                    "backspin_rpm": 2500.0,
+                    "sidespin_rpm": 300.0,
+                }
+            ]
+        })
+        .to_string();
+
+        let batch_json = calculate_derived_values_batch(&batch_input)
+            .expect("batch call should succeed for a well-formed shot");
+        let batch_results: Vec<Value> =
+            serde_json::from_str(&batch_json).expect("batch output should be a JSON array");
+        let batch_derived = &batch_results[0]["open_golf_coach"];
+
+        let single_json = calculate_derived_values_augmented(&serde_json::json!({
+            "ball_speed_meters_per_second": 70.0,
+            "vertical_launch_angle_degrees": 12.5,
+            "backspin_rpm": 2500.0,
+            "sidespin_rpm": 300.0,
+        }).to_string())
+        .expect("single-shot call should succeed for the same shot");
+        let single_result: Value =
+            serde_json::from_str(&single_json).expect("single-shot output should be JSON");
+        let single_derived = &single_result["open_golf_coach"];
+
+        // This is the regression the original chunk0-6 commit missed: batch
+        // calls bypassed augment_with_spin_and_break entirely and returned
+        // bare core output with no spin/break fields at all.
+        for field in ["total_spin_rpm", "spin_axis_degrees", "air_density_kg_per_m3"] {
+            assert!(
+                batch_derived.get(field).is_some(),
+                "batch result missing augmented field {field}"
+            );
+            assert_eq!(
+                batch_derived[field], single_derived[field],
+                "batch and single-shot augmentation diverged on {field}"
+            );
+        }
+    }
+
+    #[test]
+    fn search_launch_grid_covers_every_angle_spin_combination() {
+        let base_input = serde_json::json!({});
+        let angles = [10.0, 15.0].into_iter();
+        let spins = [2000.0, 2500.0, 3000.0].into_iter();
+
+        let grid = search_launch_grid(&base_input, 70.0, angles, spins);
+
+        assert_eq!(
+            grid.len(),
+            6,
+            "expected one result per (angle, spin) pair with no failures"
+        );
+        for (angle, spin, carry) in &grid {
+            assert!([10.0, 15.0].contains(angle));
+            assert!([2000.0, 2500.0, 3000.0].contains(spin));
+            assert!(*carry > 0.0, "carry distance should be positive");
+        }
+    }
+
+    #[test]
+    fn evaluate_carry_tracks_the_local_integrator_even_without_overrides() {
+        // A grid search's base_input is typically just the ball speed, with
+        // angle/spin swept as the trial variables — no environmental or
+        // coefficient override fields at all. Before the chunk0-2 fix this
+        // left `is_non_default` false for every cell, so the search silently
+        // read carry back from `opengolfcoach`'s own constant-Cd/Cl core
+        // instead of exercising the spin-dependent model it was built to
+        // search over. Fixing `spin_axis_degrees` to pure backspin makes the
+        // backspin/sidespin this produces fully determined by `spin_rpm`,
+        // independent of however the core itself would have resolved a bare
+        // total_spin_rpm.
+        let base_input = serde_json::json!({ "spin_axis_degrees": 0.0 });
+        let ball_speed = 70.0;
+        let angle = 12.0;
+
+        for spin_rpm in [1500.0, 3500.0] {
+            let carry = evaluate_carry(&base_input, ball_speed, angle, spin_rpm)
+                .expect("evaluate_carry should produce a carry distance");
+            let expected = trajectory::simulate(
+                ball_speed,
+                angle,
+                0.0,
+                spin_rpm,
+                0.0,
+                &Conditions::default(),
+            )
+            .expect("direct simulation should land within the iteration budget")
+            .carry_distance_meters;
+
+            assert!(
+                (carry - expected).abs() < 1e-6,
+                "evaluate_carry({spin_rpm} rpm) = {carry}, expected {expected} from the local integrator"
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_spin_round_trips_total_and_axis_to_backspin_and_sidespin() {
+        let input = serde_json::json!({
+            "total_spin_rpm": 3000.0,
+            "spin_axis_degrees": 30.0,
+        });
+        let empty_derived = serde_json::Map::new();
+
+        let (total_rpm, axis_degrees, backspin_rpm, sidespin_rpm) =
+            resolve_spin(&input, &empty_derived).expect("total/axis input should resolve");
+
+        assert!((total_rpm - 3000.0).abs() < 1e-9);
+        assert!((axis_degrees - 30.0).abs() < 1e-9);
+
+        // Round-tripping backspin/sidespin back through the other branch of
+        // resolve_spin (input carries backspin/sidespin instead) should
+        // recover the same total/axis.
+        let round_trip_input = serde_json::json!({
+            "backspin_rpm": backspin_rpm,
+            "sidespin_rpm": sidespin_rpm,
+        });
+        let (round_trip_total, round_trip_axis, round_trip_backspin, round_trip_sidespin) =
+            resolve_spin(&round_trip_input, &empty_derived)
+                .expect("backspin/sidespin input should resolve");
+
+        assert!((round_trip_total - total_rpm).abs() < 1e-9);
+        assert!((round_trip_axis - axis_degrees).abs() < 1e-9);
+        assert!((round_trip_backspin - backspin_rpm).abs() < 1e-9);
+        assert!((round_trip_sidespin - sidespin_rpm).abs() < 1e-9);
+    }
+
+    #[test]
+    fn resolve_spin_falls_back_to_already_derived_backspin_and_sidespin() {
+        let input = serde_json::json!({});
+        let mut derived = serde_json::Map::new();
+        derived.insert("backspin_rpm".to_string(), serde_json::json!(2000.0));
+        derived.insert("sidespin_rpm".to_string(), serde_json::json!(500.0));
+
+        let (total_rpm, _, backspin_rpm, sidespin_rpm) =
+            resolve_spin(&input, &derived).expect("derived backspin/sidespin should resolve");
+
+        assert!((backspin_rpm - 2000.0).abs() < 1e-9);
+        assert!((sidespin_rpm - 500.0).abs() < 1e-9);
+        assert!((total_rpm - 2000.0_f64.hypot(500.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn resolve_spin_returns_none_without_any_spin_fields() {
+        let input = serde_json::json!({ "ball_speed_meters_per_second": 70.0 });
+        let empty_derived = serde_json::Map::new();
+
+        assert!(resolve_spin(&input, &empty_derived).is_none());
+    }
+}
@@ -0,0 +1,532 @@
+//! Local, environment-aware flight-path integrator.
+//!
+//! `opengolfcoach::calculate_derived_values` simulates carry/total/offline
+//! distance assuming ISA sea-level air and has no wind model or overridable
+//! aerodynamic coefficients. This module re-implements the integration step
+//! locally so that playing conditions (elevation, temperature, humidity,
+//! sea-level pressure, wind) and aerodynamic coefficient overrides actually
+//! change the reported numbers instead of being accepted and silently
+//! ignored.
+//!
+//! Coordinates match the rest of the crate: +X forward, +Y right, +Z up
+//! (left-handed, matches Unreal natively).
+
+use serde_json::Value;
+use std::f64::consts::PI;
+
+const BALL_RADIUS_M: f64 = 0.02134;
+const BALL_MASS_KG: f64 = 0.04593;
+const GRAVITY_MPS2: f64 = 9.81;
+const NATIVE_RATE_HZ: f64 = 500.0;
+const DELTA_TIME_S: f64 = 1.0 / NATIVE_RATE_HZ;
+const MAX_ITERATIONS: usize = 10_000;
+
+// Matches the ISA-ish default playing conditions `opengolfcoach` itself
+// assumes, so a shot with no environmental fields at all sees the same
+// assumptions whether it's scored against the core's trajectory or this one.
+const DEFAULT_ELEVATION_M: f64 = 0.0;
+const DEFAULT_TEMPERATURE_C: f64 = 25.0;
+const DEFAULT_HUMIDITY_PERCENT: f64 = 50.0;
+
+// Default spin-dependent drag/lift coefficient parameters, used whenever the
+// caller doesn't override the relevant pair (cd0+cd_spin, or cl0+cl1).
+// Cd = cd0 + cd_spin * S^2, Cl = cl0 * S / (cl1 + S), where S is the
+// dimensionless spin factor BALL_RADIUS_M * spin_rad_s / relative_speed.
+const DEFAULT_CD0: f64 = 0.225;
+const DEFAULT_CD_SPIN: f64 = 0.12;
+const DEFAULT_CL0: f64 = 1.5;
+const DEFAULT_CL1: f64 = 0.3;
+
+/// Playing conditions for one simulated shot, with every field independently
+/// optional in the input JSON and defaulted otherwise.
+#[derive(Debug, Clone, Copy)]
+pub struct Conditions {
+    pub elevation_m: f64,
+    pub temperature_c: f64,
+    pub pressure_hpa: Option<f64>,
+    pub relative_humidity_percent: f64,
+    pub wind_speed_mps: f64,
+    pub wind_direction_degrees: f64,
+    pub cd0: Option<f64>,
+    pub cd_spin: Option<f64>,
+    pub cl0: Option<f64>,
+    pub cl1: Option<f64>,
+}
+
+impl Default for Conditions {
+    fn default() -> Self {
+        Conditions {
+            elevation_m: DEFAULT_ELEVATION_M,
+            temperature_c: DEFAULT_TEMPERATURE_C,
+            pressure_hpa: None,
+            relative_humidity_percent: DEFAULT_HUMIDITY_PERCENT,
+            wind_speed_mps: 0.0,
+            wind_direction_degrees: 0.0,
+            cd0: None,
+            cd_spin: None,
+            cl0: None,
+            cl1: None,
+        }
+    }
+}
+
+impl Conditions {
+    /// Read playing conditions from a shot's input fields, defaulting any
+    /// field the caller didn't supply.
+    pub fn from_input(input: &Value) -> Self {
+        let field = |key: &str| input.get(key).and_then(Value::as_f64);
+        let defaults = Conditions::default();
+
+        Conditions {
+            elevation_m: field("elevation_meters").unwrap_or(defaults.elevation_m),
+            temperature_c: field("temperature_celsius").unwrap_or(defaults.temperature_c),
+            pressure_hpa: field("pressure_hpa"),
+            relative_humidity_percent: field("relative_humidity_percent")
+                .unwrap_or(defaults.relative_humidity_percent),
+            wind_speed_mps: field("wind_speed_meters_per_second").unwrap_or(0.0),
+            wind_direction_degrees: field("wind_direction_degrees").unwrap_or(0.0),
+            cd0: field("cd0"),
+            cd_spin: field("cd_spin"),
+            cl0: field("cl0"),
+            cl1: field("cl1"),
+        }
+    }
+
+    /// Drag coefficient parameters (cd0, cd_spin), used as `Cd = cd0 +
+    /// cd_spin * S^2`. Both `cd0` and `cd_spin` must be supplied together to
+    /// override the default pair; a partial override falls back to defaults
+    /// entirely rather than mixing a caller value with a default one.
+    fn drag_coefficient_params(&self) -> (f64, f64) {
+        match (self.cd0, self.cd_spin) {
+            (Some(cd0), Some(cd_spin)) => (cd0, cd_spin),
+            _ => (DEFAULT_CD0, DEFAULT_CD_SPIN),
+        }
+    }
+
+    /// Lift coefficient parameters (cl0, cl1), used as `Cl = cl0 * S / (cl1 +
+    /// S)`. Both `cl0` and `cl1` must be supplied together to override the
+    /// default pair.
+    fn lift_coefficient_params(&self) -> (f64, f64) {
+        match (self.cl0, self.cl1) {
+            (Some(cl0), Some(cl1)) => (cl0, cl1),
+            _ => (DEFAULT_CL0, DEFAULT_CL1),
+        }
+    }
+}
+
+/// Spin-dependent drag coefficient: `Cd = cd0 + cd_spin * S^2`.
+fn drag_coefficient(conditions: &Conditions, spin_factor: f64) -> f64 {
+    let (cd0, cd_spin) = conditions.drag_coefficient_params();
+    cd0 + cd_spin * spin_factor.powi(2)
+}
+
+/// Spin-dependent lift coefficient: `Cl = cl0 * S / (cl1 + S)`.
+fn lift_coefficient(conditions: &Conditions, spin_factor: f64) -> f64 {
+    let (cl0, cl1) = conditions.lift_coefficient_params();
+    let denominator = cl1 + spin_factor;
+    if denominator == 0.0 {
+        0.0
+    } else {
+        cl0 * spin_factor / denominator
+    }
+}
+
+/// Saturation vapor pressure via the Tetens formula [hPa].
+fn tetens_saturation_vapor_pressure_hpa(temp_c: f64) -> f64 {
+    6.1078 * 10f64.powf((7.5 * temp_c) / (temp_c + 237.3))
+}
+
+/// Station pressure at `elevation_m` given sea-level pressure, via the
+/// barometric formula.
+fn barometric_pressure_hpa(elevation_m: f64, sea_level_hpa: f64) -> f64 {
+    const T0: f64 = 288.15; // sea-level standard temperature (K)
+    const LAPSE_RATE: f64 = 0.0065; // K/m
+    const GRAVITY: f64 = 9.80665; // m/s^2
+    const MOLAR_MASS_AIR: f64 = 0.0289644; // kg/mol
+    const GAS_CONSTANT: f64 = 8.3144598; // J/(mol*K)
+
+    sea_level_hpa
+        * (1.0 - (LAPSE_RATE * elevation_m) / T0).powf((GRAVITY * MOLAR_MASS_AIR) / (GAS_CONSTANT * LAPSE_RATE))
+}
+
+/// Moist air density via the ideal gas law, splitting station pressure into
+/// dry-air and water-vapor partial pressures derived from relative humidity
+/// (Tetens formula).
+///
+/// `pressure_hpa` is treated as a sea-level-referenced reading (as weather
+/// stations and forecast APIs report it) and always run through the
+/// barometric formula against `elevation_m`; at `elevation_m == 0.0` this is
+/// a no-op, so a supplied reading without an elevation is used as-is.
+pub fn air_density_kg_per_m3(conditions: &Conditions) -> f64 {
+    let temp_k = conditions.temperature_c + 273.15;
+    let sea_level_hpa = conditions.pressure_hpa.unwrap_or(1013.25);
+    let station_hpa = barometric_pressure_hpa(conditions.elevation_m, sea_level_hpa);
+
+    let saturation_hpa = tetens_saturation_vapor_pressure_hpa(conditions.temperature_c);
+    let vapor_hpa = (conditions.relative_humidity_percent / 100.0) * saturation_hpa;
+    let dry_hpa = (station_hpa - vapor_hpa).max(0.0);
+
+    const R_DRY: f64 = 287.058; // J/(kg*K)
+    const R_VAPOR: f64 = 461.495; // J/(kg*K)
+
+    (dry_hpa * 100.0) / (R_DRY * temp_k) + (vapor_hpa * 100.0) / (R_VAPOR * temp_k)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Vec3 {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Vec3 {
+    fn new(x: f64, y: f64, z: f64) -> Self {
+        Vec3 { x, y, z }
+    }
+    fn magnitude(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+    fn sub(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+    fn add(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+    fn scale(&self, s: f64) -> Vec3 {
+        Vec3::new(self.x * s, self.y * s, self.z * s)
+    }
+    fn normalize(&self) -> Vec3 {
+        let m = self.magnitude();
+        if m == 0.0 {
+            Vec3::new(0.0, 0.0, 0.0)
+        } else {
+            self.scale(1.0 / m)
+        }
+    }
+    fn cross(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+/// One sample of the locally-simulated flight path.
+#[derive(Debug, Clone, Copy)]
+pub struct TrajectoryPoint {
+    pub time_seconds: f64,
+    pub x_meters: f64,
+    pub y_meters: f64,
+    pub z_meters: f64,
+    pub speed_meters_per_second: f64,
+    pub spin_rpm: f64,
+}
+
+/// Result of simulating a shot under a set of `Conditions`.
+pub struct TrajectoryResult {
+    pub points: Vec<TrajectoryPoint>,
+    pub air_density_kg_per_m3: f64,
+    pub drag_coefficient_at_launch: f64,
+    pub lift_coefficient_at_launch: f64,
+    pub carry_distance_meters: f64,
+    pub offline_distance_meters: f64,
+    pub apex_height_meters: f64,
+    pub descent_angle_degrees: f64,
+    pub flight_time_seconds: f64,
+}
+
+/// Simulate the shot under `conditions` with an explicit Euler velocity
+/// update and an average-velocity (trapezoidal) position update, at the same
+/// 500 Hz native rate as `opengolfcoach`'s own integrator.
+///
+/// Wind is only subtracted from velocity for the aerodynamic force
+/// calculation (drag/lift act on velocity *relative to the air*); gravity
+/// and the resulting position/velocity update use the ball's true
+/// ground-relative velocity.
+///
+/// Returns `None` if the ball doesn't land within `MAX_ITERATIONS` steps
+/// (e.g. a tailwind/lift combination strong enough to keep it aloft), rather
+/// than reporting landing coordinates that never converged.
+pub fn simulate(
+    ball_speed_mps: f64,
+    v_launch_deg: f64,
+    h_launch_deg: f64,
+    backspin_rpm: f64,
+    sidespin_rpm: f64,
+    conditions: &Conditions,
+) -> Option<TrajectoryResult> {
+    let v_launch_rad = v_launch_deg * PI / 180.0;
+    let h_launch_rad = h_launch_deg * PI / 180.0;
+
+    let backspin_rad_s = backspin_rpm * 0.10472;
+    let sidespin_rad_s = sidespin_rpm * 0.10472;
+    let mut total_spin_rad_s = backspin_rad_s.hypot(sidespin_rad_s);
+    let spin_axis_rad = sidespin_rad_s.atan2(backspin_rad_s);
+    let spin_axis_vec = Vec3::new(0.0, -spin_axis_rad.cos(), spin_axis_rad.sin()).normalize();
+
+    let air_density = air_density_kg_per_m3(conditions);
+    let cross_section_m2 = PI * BALL_RADIUS_M * BALL_RADIUS_M;
+
+    let wind_rad = conditions.wind_direction_degrees * PI / 180.0;
+    let wind = Vec3::new(
+        conditions.wind_speed_mps * wind_rad.cos(),
+        conditions.wind_speed_mps * wind_rad.sin(),
+        0.0,
+    );
+
+    let v_horizontal = ball_speed_mps * v_launch_rad.cos();
+    let mut position = Vec3::new(0.0, 0.0, 0.0);
+    let mut velocity = Vec3::new(
+        v_horizontal * h_launch_rad.cos(),
+        v_horizontal * h_launch_rad.sin(),
+        ball_speed_mps * v_launch_rad.sin(),
+    );
+
+    let mut points = Vec::new();
+    let mut time = 0.0;
+    let mut iteration = 0;
+    let mut drag_coefficient_at_launch = 0.0;
+    let mut lift_coefficient_at_launch = 0.0;
+
+    points.push(TrajectoryPoint {
+        time_seconds: time,
+        x_meters: position.x,
+        y_meters: position.y,
+        z_meters: position.z,
+        speed_meters_per_second: velocity.magnitude(),
+        spin_rpm: total_spin_rad_s / 0.10472,
+    });
+
+    while position.z >= 0.0 && iteration < MAX_ITERATIONS {
+        let relative_velocity = velocity.sub(&wind);
+        let relative_speed = relative_velocity.magnitude();
+
+        let spin_factor = if relative_speed == 0.0 {
+            0.0
+        } else {
+            BALL_RADIUS_M * total_spin_rad_s / relative_speed
+        };
+        let drag_coefficient = drag_coefficient(conditions, spin_factor);
+        let lift_coefficient = lift_coefficient(conditions, spin_factor);
+        if iteration == 0 {
+            drag_coefficient_at_launch = drag_coefficient;
+            lift_coefficient_at_launch = lift_coefficient;
+        }
+
+        let dynamic_pressure = 0.5 * air_density * cross_section_m2 * relative_speed.powi(2);
+        let drag_force = relative_velocity
+            .normalize()
+            .scale(-dynamic_pressure * drag_coefficient);
+
+        let lift_dir = spin_axis_vec.cross(&relative_velocity.normalize()).normalize();
+        let lift_force = lift_dir.scale(dynamic_pressure * lift_coefficient);
+
+        let total_force = drag_force.add(&lift_force);
+        let acceleration = Vec3::new(
+            total_force.x / BALL_MASS_KG,
+            total_force.y / BALL_MASS_KG,
+            total_force.z / BALL_MASS_KG - GRAVITY_MPS2,
+        );
+
+        let new_velocity = velocity.add(&acceleration.scale(DELTA_TIME_S));
+        let average_velocity = velocity.add(&new_velocity).scale(0.5);
+        position = position.add(&average_velocity.scale(DELTA_TIME_S));
+        velocity = new_velocity;
+
+        // Aerodynamic spin decay, proportional to ground speed (matches the
+        // decay constant `opengolfcoach::trajectory` uses for the same
+        // Smits & Smith 1994 wind-tunnel fit).
+        const SPIN_DECAY_PER_METER: f64 = 0.001;
+        total_spin_rad_s *= (-SPIN_DECAY_PER_METER * velocity.magnitude() * DELTA_TIME_S).exp();
+
+        time += DELTA_TIME_S;
+        points.push(TrajectoryPoint {
+            time_seconds: time,
+            x_meters: position.x,
+            y_meters: position.y,
+            z_meters: position.z,
+            speed_meters_per_second: velocity.magnitude(),
+            spin_rpm: total_spin_rad_s / 0.10472,
+        });
+        iteration += 1;
+    }
+
+    if position.z >= 0.0 {
+        return None;
+    }
+
+    let landing = *points.last().expect("at least the initial point");
+    let apex_height_meters = points
+        .iter()
+        .map(|p| p.z_meters)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let horizontal_speed = (velocity.x.powi(2) + velocity.y.powi(2)).sqrt();
+    let descent_angle_degrees = (-velocity.z).atan2(horizontal_speed) * 180.0 / PI;
+
+    Some(TrajectoryResult {
+        carry_distance_meters: (landing.x_meters.powi(2) + landing.y_meters.powi(2)).sqrt(),
+        offline_distance_meters: landing.y_meters,
+        apex_height_meters,
+        descent_angle_degrees,
+        flight_time_seconds: landing.time_seconds,
+        air_density_kg_per_m3: air_density,
+        drag_coefficient_at_launch,
+        lift_coefficient_at_launch,
+        points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn air_density_kg_per_m3_matches_expected_value_under_default_conditions() {
+        let density = air_density_kg_per_m3(&Conditions::default());
+        assert!(
+            (density - 1.1769).abs() < 1e-3,
+            "got {density}, expected ~1.1769 kg/m^3 at the module's own ISA-ish defaults (25C, 50% RH, sea level)"
+        );
+    }
+
+    #[test]
+    fn barometric_pressure_hpa_decreases_with_elevation() {
+        let sea_level = barometric_pressure_hpa(0.0, 1013.25);
+        let at_1500m = barometric_pressure_hpa(1500.0, 1013.25);
+
+        assert_eq!(sea_level, 1013.25, "no-op at sea level");
+        assert!(
+            at_1500m < sea_level,
+            "pressure should drop with elevation, got {at_1500m} at 1500m vs {sea_level} at sea level"
+        );
+        assert!(
+            (at_1500m - 845.56).abs() < 0.1,
+            "got {at_1500m}, expected ~845.56 hPa at 1500m from a 1013.25 hPa sea-level reading"
+        );
+    }
+
+    #[test]
+    fn tetens_saturation_vapor_pressure_hpa_increases_with_temperature() {
+        let at_0c = tetens_saturation_vapor_pressure_hpa(0.0);
+        let at_25c = tetens_saturation_vapor_pressure_hpa(25.0);
+
+        assert!((at_0c - 6.1078).abs() < 1e-3, "got {at_0c}, expected ~6.1078 hPa at 0C");
+        assert!(
+            at_25c > at_0c,
+            "saturation vapor pressure should rise with temperature, got {at_25c} at 25C vs {at_0c} at 0C"
+        );
+    }
+
+    #[test]
+    fn drag_coefficient_increases_with_spin_factor_squared() {
+        let conditions = Conditions::default();
+
+        assert_eq!(
+            drag_coefficient(&conditions, 0.0),
+            DEFAULT_CD0,
+            "with no spin, Cd should be exactly cd0"
+        );
+        let with_spin = drag_coefficient(&conditions, 0.1);
+        assert!(
+            (with_spin - (DEFAULT_CD0 + DEFAULT_CD_SPIN * 0.1_f64.powi(2))).abs() < 1e-9,
+            "got {with_spin}, expected cd0 + cd_spin * spin_factor^2"
+        );
+        assert!(with_spin > DEFAULT_CD0, "drag should increase with spin");
+    }
+
+    #[test]
+    fn lift_coefficient_follows_cl0_times_s_over_cl1_plus_s() {
+        let conditions = Conditions::default();
+
+        assert_eq!(
+            lift_coefficient(&conditions, 0.0),
+            0.0,
+            "with no spin, Cl should be zero"
+        );
+        let with_spin = lift_coefficient(&conditions, 0.1);
+        let expected = DEFAULT_CL0 * 0.1 / (DEFAULT_CL1 + 0.1);
+        assert!(
+            (with_spin - expected).abs() < 1e-9,
+            "got {with_spin}, expected cl0 * S / (cl1 + S) = {expected}"
+        );
+    }
+
+    #[test]
+    fn lift_coefficient_handles_a_zero_denominator_without_dividing_by_zero() {
+        let conditions = Conditions {
+            cl0: Some(5.0),
+            cl1: Some(0.0),
+            ..Conditions::default()
+        };
+
+        assert_eq!(
+            lift_coefficient(&conditions, 0.0),
+            0.0,
+            "cl1 + spin_factor == 0 should short-circuit to 0 rather than divide by zero"
+        );
+    }
+
+    #[test]
+    fn higher_backspin_raises_apex_height() {
+        let conditions = Conditions::default();
+        let no_spin = simulate(65.0, 12.0, 0.0, 0.0, 0.0, &conditions)
+            .expect("should land within the iteration budget");
+        let with_backspin = simulate(65.0, 12.0, 0.0, 3000.0, 0.0, &conditions)
+            .expect("should land within the iteration budget");
+
+        assert!(
+            with_backspin.apex_height_meters > no_spin.apex_height_meters,
+            "backspin apex {} should exceed no-spin apex {}",
+            with_backspin.apex_height_meters,
+            no_spin.apex_height_meters
+        );
+    }
+
+    #[test]
+    fn wind_changes_carry_distance() {
+        let calm = Conditions::default();
+        let windy = Conditions {
+            wind_speed_mps: 10.0,
+            wind_direction_degrees: 0.0,
+            ..Conditions::default()
+        };
+
+        let calm_result = simulate(65.0, 12.0, 0.0, 2500.0, 0.0, &calm)
+            .expect("should land within the iteration budget");
+        let windy_result = simulate(65.0, 12.0, 0.0, 2500.0, 0.0, &windy)
+            .expect("should land within the iteration budget");
+
+        assert!(
+            (calm_result.carry_distance_meters - windy_result.carry_distance_meters).abs() > 1.0,
+            "a 10 m/s wind along the line of play should noticeably change carry distance, \
+             got calm={} windy={}",
+            calm_result.carry_distance_meters,
+            windy_result.carry_distance_meters
+        );
+    }
+
+    #[test]
+    fn zeroing_lift_coefficients_changes_the_landing_point() {
+        // This is the premise `compute_magnus_break` (lib.rs) relies on:
+        // re-simulating the same shot with lift zeroed out and differencing
+        // the landing point isolates the Magnus-force contribution.
+        let with_lift = Conditions::default();
+        let without_lift = Conditions {
+            cl0: Some(0.0),
+            cl1: Some(1.0),
+            ..Conditions::default()
+        };
+
+        let with_lift_result = simulate(65.0, 12.0, 0.0, 2500.0, 500.0, &with_lift)
+            .expect("should land within the iteration budget");
+        let without_lift_result = simulate(65.0, 12.0, 0.0, 2500.0, 500.0, &without_lift)
+            .expect("should land within the iteration budget");
+
+        assert!(
+            (with_lift_result.offline_distance_meters - without_lift_result.offline_distance_meters).abs()
+                > 1e-6,
+            "zeroing lift should change the landing offline distance"
+        );
+    }
+}